@@ -0,0 +1,44 @@
+//! `cargo-audit` Abscissa application
+
+use crate::{commands::CargoAuditCommand, config::AuditConfig};
+use abscissa_core::{
+    application::{self, AppCell},
+    config::CfgCell,
+    Application, StandardPaths,
+};
+
+/// Application state
+pub static APP: AppCell<CargoAuditApplication> = AppCell::new();
+
+/// `cargo-audit` Abscissa Application
+#[derive(Debug)]
+pub struct CargoAuditApplication {
+    /// Application configuration
+    config: CfgCell<AuditConfig>,
+
+    /// Application state
+    state: application::State<Self>,
+}
+
+impl Default for CargoAuditApplication {
+    fn default() -> Self {
+        Self {
+            config: CfgCell::default(),
+            state: application::State::default(),
+        }
+    }
+}
+
+impl Application for CargoAuditApplication {
+    type Cmd = CargoAuditCommand;
+    type Cfg = AuditConfig;
+    type Paths = StandardPaths;
+
+    fn config(&self) -> std::sync::Arc<AuditConfig> {
+        self.config.read()
+    }
+
+    fn state(&self) -> &application::State<Self> {
+        &self.state
+    }
+}
@@ -0,0 +1,697 @@
+//! Core auditing logic: loading advisory databases, matching them against
+//! a `Cargo.lock`, and producing a `Report`.
+
+use crate::{
+    config::{AuditConfig, IgnoreAdvisoryId, LintLevel, Severity},
+    prelude::*,
+};
+use chrono::NaiveDate;
+use rustsec::{
+    advisory::Id as AdvisoryId, warning::Kind as WarningKind, Advisory, Database, Lockfile,
+    Vulnerability, Warning,
+};
+use semver::{Version, VersionReq};
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+    process::exit,
+};
+
+/// Default advisory DB path, relative to the user's home directory
+const DEFAULT_ADVISORY_DB_PATH: &str = ".cargo/advisory-db";
+
+/// Default advisory DB git repository URL
+const DEFAULT_ADVISORY_DB_URL: &str = "https://github.com/RustSec/advisory-db.git";
+
+/// Result of auditing a `Cargo.lock` against one or more advisory databases
+#[derive(Debug, Default, serde::Serialize)]
+pub struct Report {
+    /// Vulnerabilities found, merged and de-duplicated by advisory ID
+    /// across every configured database.
+    pub vulnerabilities: Vec<Vulnerability>,
+
+    /// Informational warnings (unmaintained, yanked, notice), likewise
+    /// merged and de-duplicated across every configured database.
+    pub warnings: Vec<Warning>,
+
+    /// Active vs. expired breakdown of the configured ignore list
+    pub exemptions: ExemptionSummary,
+}
+
+/// Active vs. expired breakdown of the configured ignore list, as of the
+/// date the audit ran
+#[derive(Clone, Debug, Default, serde::Serialize)]
+pub struct ExemptionSummary {
+    /// Number of ignore entries still in effect
+    pub active: usize,
+
+    /// Advisory IDs whose ignore entry has lapsed and is no longer honored
+    pub expired: Vec<AdvisoryId>,
+}
+
+/// Performs a security audit of a `Cargo.lock` file
+pub struct Auditor {
+    /// Audit configuration
+    config: AuditConfig,
+}
+
+impl Auditor {
+    /// Initialize a new `Auditor`
+    pub fn new(config: &AuditConfig) -> Self {
+        Self {
+            config: config.clone(),
+        }
+    }
+
+    /// Audit the lockfile at `lockfile_path`, querying every configured
+    /// advisory database and reporting the union of their findings.
+    pub fn audit(&self, lockfile_path: &Path) -> Report {
+        let lockfile = Lockfile::load(lockfile_path).unwrap_or_else(|e| {
+            status_err!("couldn't load {}: {}", lockfile_path.display(), e);
+            exit(1);
+        });
+
+        let today = chrono::Local::now().date_naive();
+        let (active_ignores, exemptions) = partition_ignores(&self.config.ignore, today);
+
+        for expired in &exemptions.expired {
+            status_warn!(
+                "ignore exemption for {} has expired; re-reporting it",
+                expired
+            );
+        }
+
+        let databases = self.load_databases();
+        let mut vulnerabilities = vec![];
+        let mut warnings = vec![];
+
+        for db in &databases {
+            let db_report = rustsec::Report::generate(db, &lockfile, &self.settings(&active_ignores));
+            vulnerabilities.extend(db_report.vulnerabilities.list);
+            warnings.extend(db_report.warnings.into_iter().flat_map(|(_, w)| w));
+        }
+
+        Report {
+            // Keyed on (advisory id, package name, package version) rather
+            // than advisory id alone: two vulnerable versions of the same
+            // crate share an advisory ID, and collapsing on the ID alone
+            // would silently drop one of them. Only identical matches
+            // reported by more than one database should merge.
+            vulnerabilities: dedup_by(vulnerabilities, |v| {
+                (
+                    v.advisory.id.clone(),
+                    v.package.name.to_string(),
+                    v.package.version.to_string(),
+                )
+            }),
+            warnings: dedup_by(warnings, |w| {
+                w.kind.to_string() + &w.package.name.to_string() + &w.package.version.to_string()
+            }),
+            exemptions,
+        }
+    }
+
+    /// Build the list of advisory databases to query: one per configured
+    /// `--db`/`advisory_db_paths` entry, or the default RustSec DB path if
+    /// none were given.
+    fn load_databases(&self) -> Vec<Database> {
+        let paths = if self.config.advisory_db_paths.is_empty() {
+            vec![default_advisory_db_path()]
+        } else {
+            self.config.advisory_db_paths.clone()
+        };
+
+        paths
+            .iter()
+            .enumerate()
+            .map(|(i, path)| self.load_database(path, self.advisory_db_url(i)))
+            .collect()
+    }
+
+    /// Load a single advisory database: straight off disk in `--offline`
+    /// mode (never touching the network), by `git fetch` when allowed, or
+    /// by opening the existing local git checkout otherwise.
+    fn load_database(&self, path: &Path, url: &str) -> Database {
+        if self.config.offline {
+            load_offline_database(path)
+        } else if !self.config.no_fetch {
+            Database::fetch_and_load(url, path).unwrap_or_else(|e| {
+                status_err!("couldn't fetch advisory database from {}: {}", url, e);
+                exit(1);
+            })
+        } else {
+            Database::open(path).unwrap_or_else(|e| {
+                status_err!("couldn't load advisory database from {}: {}", path.display(), e);
+                exit(1);
+            })
+        }
+    }
+
+    /// URL to use for the `i`th configured database path
+    fn advisory_db_url(&self, i: usize) -> &str {
+        self.config
+            .advisory_db_urls
+            .get(i)
+            .map(String::as_str)
+            .unwrap_or(DEFAULT_ADVISORY_DB_URL)
+    }
+
+    /// Build the `rustsec::report::Settings` used to query each database,
+    /// honoring only the ignore entries in `active_ignores` (expired
+    /// entries have already been filtered out by the caller).
+    fn settings(&self, active_ignores: &[AdvisoryId]) -> rustsec::report::Settings {
+        let mut settings = rustsec::report::Settings::default();
+        settings.target_arch = self.config.target_arch;
+        settings.target_os = self.config.target_os;
+        settings.ignore = active_ignores.to_vec();
+        settings
+    }
+
+    /// Attempt to remediate every vulnerability in `report` by bumping the
+    /// affected package to the lowest version that both satisfies the
+    /// advisory's `patched` requirement and stays within the package's
+    /// existing semver compatibility range, then rewrite `Cargo.lock`
+    /// (unless `dry_run` is set).
+    pub fn fix(&self, lockfile_path: &Path, report: &Report, dry_run: bool) -> FixReport {
+        let mut lockfile = Lockfile::load(lockfile_path).unwrap_or_else(|e| {
+            status_err!("couldn't load {}: {}", lockfile_path.display(), e);
+            exit(1);
+        });
+
+        let mut fix_report = FixReport::default();
+
+        for vuln in &report.vulnerabilities {
+            let name = vuln.package.name.clone();
+            let from_version = vuln.package.version.clone();
+            let constraint = compatible_version_req(&from_version);
+
+            match lowest_patched_version(&vuln.advisory, &constraint) {
+                Some(to_version) => {
+                    if !dry_run {
+                        rewrite_lockfile_entry(&mut lockfile, &name, &from_version, &to_version);
+                    }
+
+                    fix_report.fixes.push(Fix {
+                        package: name.to_string(),
+                        from_version,
+                        to_version,
+                        advisory_id: vuln.advisory.id.clone(),
+                    });
+                }
+                None => fix_report.unfixable.push(Unfixable {
+                    package: name.to_string(),
+                    version: from_version,
+                    advisory_id: vuln.advisory.id.clone(),
+                }),
+            }
+        }
+
+        if dry_run {
+            for fix in &fix_report.fixes {
+                eprintln!(
+                    "would bump {} {} -> {} ({})",
+                    fix.package, fix.from_version, fix.to_version, fix.advisory_id
+                );
+            }
+        } else if !fix_report.fixes.is_empty() {
+            lockfile.save(lockfile_path).unwrap_or_else(|e| {
+                status_err!("couldn't write {}: {}", lockfile_path.display(), e);
+                exit(1);
+            });
+        }
+
+        for unfixable in &fix_report.unfixable {
+            status_err!(
+                "no patched version available for {} {} ({})",
+                unfixable.package,
+                unfixable.version,
+                unfixable.advisory_id
+            );
+        }
+
+        fix_report
+    }
+
+    /// Apply this audit's configured lint levels and severity threshold to
+    /// `report`, partitioning its findings into ones that should fail the
+    /// build (`deny`) and ones that should merely be printed (`warn`).
+    pub fn lint(&self, report: &Report) -> LintOutcome {
+        let mut outcome = LintOutcome::default();
+
+        for vuln in &report.vulnerabilities {
+            let severity = vulnerability_severity(vuln);
+            let level = classify(
+                self.config.lint.vulnerability,
+                severity,
+                self.config.severity_threshold,
+            );
+            outcome.record(level, format!("{}: {}", vuln.advisory.id, vuln.package.name));
+        }
+
+        for warning in &report.warnings {
+            let configured_level = match warning.kind {
+                WarningKind::Unmaintained => self.config.lint.unmaintained,
+                WarningKind::Unsound => self.config.lint.unsound,
+                WarningKind::Yanked => self.config.lint.yanked,
+                _ => self.config.lint.notice,
+            };
+            outcome.record(
+                configured_level,
+                format!("{}: {}", warning.kind, warning.package.name),
+            );
+        }
+
+        outcome
+    }
+}
+
+/// Outcome of [`Auditor::lint`]: findings partitioned by whether they
+/// should fail the build or merely be printed
+#[derive(Clone, Debug, Default)]
+pub struct LintOutcome {
+    /// Findings at `deny` level: cause a nonzero exit code
+    pub deny: Vec<String>,
+
+    /// Findings at `warn` level: printed, but don't affect the exit code
+    pub warn: Vec<String>,
+}
+
+impl LintOutcome {
+    /// Process exit code `cargo audit` should use for this outcome
+    pub fn exit_code(&self) -> i32 {
+        if self.deny.is_empty() {
+            0
+        } else {
+            1
+        }
+    }
+
+    fn record(&mut self, level: LintLevel, description: String) {
+        match level {
+            LintLevel::Deny => self.deny.push(description),
+            LintLevel::Warn => self.warn.push(description),
+            LintLevel::Allow => {}
+        }
+    }
+}
+
+/// CVSS-derived severity of a vulnerability, or `None` if the advisory
+/// carries no CVSS score
+fn vulnerability_severity(vuln: &Vulnerability) -> Option<Severity> {
+    let cvss = vuln.advisory.metadata.cvss.as_ref()?;
+
+    Some(match cvss.severity() {
+        rustsec::cvss::Severity::None => Severity::None,
+        rustsec::cvss::Severity::Low => Severity::Low,
+        rustsec::cvss::Severity::Medium => Severity::Medium,
+        rustsec::cvss::Severity::High => Severity::High,
+        rustsec::cvss::Severity::Critical => Severity::Critical,
+    })
+}
+
+/// Downgrade a [`LintLevel::Deny`] `level` to [`LintLevel::Warn`] when
+/// `severity` is known and falls below `threshold`; otherwise leave it as
+/// configured (`warn` and `allow` are never changed). A finding with no
+/// CVSS score is never downgraded, since there's nothing to compare
+/// against the threshold.
+fn classify(level: LintLevel, severity: Option<Severity>, threshold: Option<Severity>) -> LintLevel {
+    match (severity, threshold) {
+        (Some(severity), Some(threshold)) if level == LintLevel::Deny && severity < threshold => {
+            LintLevel::Warn
+        }
+        _ => level,
+    }
+}
+
+/// Split the configured ignore list into advisory IDs still active as of
+/// `today` (to pass to `rustsec::Report::generate`) and a summary of how
+/// many are active vs. how many have lapsed and should be re-reported.
+fn partition_ignores(
+    ignores: &[IgnoreAdvisoryId],
+    today: NaiveDate,
+) -> (Vec<AdvisoryId>, ExemptionSummary) {
+    let mut active_ids = vec![];
+    let mut summary = ExemptionSummary::default();
+
+    for entry in ignores {
+        if entry.is_expired(today) {
+            summary.expired.push(entry.id.clone());
+        } else {
+            active_ids.push(entry.id.clone());
+            summary.active += 1;
+        }
+    }
+
+    (active_ids, summary)
+}
+
+/// A proposed remediation: bump `package` from `from_version` to
+/// `to_version` to resolve `advisory_id`.
+#[derive(Clone, Debug)]
+pub struct Fix {
+    /// Name of the vulnerable package
+    pub package: String,
+
+    /// Version currently resolved in `Cargo.lock`
+    pub from_version: Version,
+
+    /// Lowest version that resolves the advisory while staying within the
+    /// package's existing semver compatibility range
+    pub to_version: Version,
+
+    /// Advisory this fix resolves
+    pub advisory_id: AdvisoryId,
+}
+
+/// A vulnerable package for which no patched release satisfying the
+/// existing dependency constraints could be found
+#[derive(Clone, Debug)]
+pub struct Unfixable {
+    /// Name of the vulnerable package
+    pub package: String,
+
+    /// Version currently resolved in `Cargo.lock`
+    pub version: Version,
+
+    /// Advisory which could not be resolved
+    pub advisory_id: AdvisoryId,
+}
+
+/// Outcome of a `cargo audit --fix` run
+#[derive(Clone, Debug, Default)]
+pub struct FixReport {
+    /// Packages that were (or, under `--dry-run`, would be) bumped
+    pub fixes: Vec<Fix>,
+
+    /// Vulnerable packages with no available patched version
+    pub unfixable: Vec<Unfixable>,
+}
+
+/// Version requirement approximating "the existing semver constraints in
+/// the dependency graph" for a package currently resolved at `current`:
+/// since `Cargo.lock` entries pin exact versions rather than carrying the
+/// requirements that produced them, we conservatively stay within
+/// `current`'s compatible range (the same range `cargo update -p` would
+/// honor) rather than risk a breaking major-version bump.
+fn compatible_version_req(current: &Version) -> VersionReq {
+    VersionReq::parse(&format!("^{}", current)).unwrap_or_else(|_| VersionReq::STAR)
+}
+
+/// Lowest version that satisfies both `constraint` and at least one of the
+/// advisory's `patched` requirements, among versions available in the
+/// package's registry index.
+fn lowest_patched_version(advisory: &Advisory, constraint: &VersionReq) -> Option<Version> {
+    let available = registry_versions(advisory.metadata.package.as_deref()?)?;
+    select_patched_version(available, constraint, &advisory.versions.patched)
+}
+
+/// Pure selection logic for [`lowest_patched_version`], split out so it can
+/// be unit tested without a registry index on disk: the lowest of
+/// `available` that satisfies both `constraint` and at least one entry in
+/// `patched`.
+fn select_patched_version(
+    available: Vec<Version>,
+    constraint: &VersionReq,
+    patched: &[VersionReq],
+) -> Option<Version> {
+    available
+        .into_iter()
+        .filter(|version| constraint.matches(version))
+        .filter(|version| patched.iter().any(|req| req.matches(version)))
+        .min()
+}
+
+/// Published versions of `package` available from its registry index
+fn registry_versions(package: &str) -> Option<Vec<Version>> {
+    crates_index::Index::new_cargo_default()
+        .ok()?
+        .crate_(package)
+        .map(|krate| {
+            krate
+                .versions()
+                .iter()
+                .filter_map(|v| Version::parse(v.version()).ok())
+                .collect()
+        })
+}
+
+/// Rewrite `name`'s entry in `lockfile` from `from_version` to
+/// `to_version`, preserving its `source` and refreshing `checksum` from
+/// the registry index so the bumped entry stays verifiable, then repoint
+/// every other package's `dependencies` entry for `name@from_version` at
+/// `to_version` so the lockfile stays internally consistent. If
+/// `to_version` is already resolved elsewhere in the tree, the stale
+/// `from_version` entry is dropped instead of leaving two entries for the
+/// same `name@version` behind.
+fn rewrite_lockfile_entry(
+    lockfile: &mut Lockfile,
+    name: &str,
+    from_version: &Version,
+    to_version: &Version,
+) {
+    let to_version_already_resolved = lockfile
+        .packages
+        .iter()
+        .any(|p| p.name.as_str() == name && &p.version == to_version);
+
+    if to_version_already_resolved {
+        lockfile
+            .packages
+            .retain(|p| !(p.name.as_str() == name && &p.version == from_version));
+    } else if let Some(package) = lockfile
+        .packages
+        .iter_mut()
+        .find(|p| p.name.as_str() == name && &p.version == from_version)
+    {
+        package.version = to_version.clone();
+        package.checksum = package
+            .source
+            .as_ref()
+            .and_then(|_| registry_checksum(name, to_version));
+    }
+
+    for package in &mut lockfile.packages {
+        for dependency in &mut package.dependencies {
+            if dependency.name.as_str() == name && &dependency.version == from_version {
+                dependency.version = to_version.clone();
+            }
+        }
+    }
+}
+
+/// Look up the checksum for `name@version` from its registry index
+fn registry_checksum(name: &str, version: &Version) -> Option<cargo_lock::package::Checksum> {
+    crates_index::Index::new_cargo_default()
+        .ok()?
+        .crate_(name)?
+        .versions()
+        .iter()
+        .find(|v| v.version() == version.to_string())
+        .map(|v| v.checksum())
+        .and_then(|digest| hex::encode(digest).parse().ok())
+}
+
+/// Default advisory database path: `~/.cargo/advisory-db`
+fn default_advisory_db_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(DEFAULT_ADVISORY_DB_PATH)
+}
+
+/// Load an advisory database straight from a plain directory of advisory
+/// TOML files (as opposed to a git-backed checkout), for `--offline` mode.
+/// Fails with a clear filesystem error rather than attempting any network
+/// access if `path` doesn't exist, since an absent vendored DB is a
+/// packaging mistake the build pipeline should surface immediately.
+fn load_offline_database(path: &Path) -> Database {
+    if !path.is_dir() {
+        status_err!(
+            "--offline advisory database directory not found: {} (vendor the advisory-db tree there before auditing offline)",
+            path.display()
+        );
+        exit(1);
+    }
+
+    let advisories = walk_advisory_toml_files(path)
+        .into_iter()
+        .map(|advisory_path| {
+            let contents = std::fs::read_to_string(&advisory_path).unwrap_or_else(|e| {
+                status_err!("couldn't read {}: {}", advisory_path.display(), e);
+                exit(1);
+            });
+
+            toml::from_str::<Advisory>(&contents).unwrap_or_else(|e| {
+                status_err!("couldn't parse {}: {}", advisory_path.display(), e);
+                exit(1);
+            })
+        })
+        .collect::<Vec<_>>();
+
+    Database::from_advisories(advisories).unwrap_or_else(|e| {
+        status_err!(
+            "couldn't build advisory database from {}: {}",
+            path.display(),
+            e
+        );
+        exit(1);
+    })
+}
+
+/// Recursively collect every `*.toml` file under `dir`
+fn walk_advisory_toml_files(dir: &Path) -> Vec<PathBuf> {
+    let mut paths = vec![];
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            status_err!("couldn't read {}: {}", dir.display(), e);
+            exit(1);
+        }
+    };
+
+    for entry in entries.filter_map(Result::ok) {
+        let path = entry.path();
+        if path.is_dir() {
+            paths.extend(walk_advisory_toml_files(&path));
+        } else if path.extension().and_then(|ext| ext.to_str()) == Some("toml") {
+            paths.push(path);
+        }
+    }
+
+    paths
+}
+
+/// De-duplicate a `Vec<T>`, keeping the first occurrence of each distinct
+/// key as produced by `key_fn`. Used to merge findings from multiple
+/// advisory databases so the same advisory ID (or warning) reported by
+/// more than one database is only surfaced once.
+fn dedup_by<T, K: Ord, F: Fn(&T) -> K>(items: Vec<T>, key_fn: F) -> Vec<T> {
+    let mut seen = BTreeSet::new();
+    items
+        .into_iter()
+        .filter(|item| seen.insert(key_fn(item)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        classify, compatible_version_req, dedup_by, partition_ignores, select_patched_version,
+        walk_advisory_toml_files,
+    };
+    use crate::config::{IgnoreAdvisoryId, LintLevel, Severity};
+    use chrono::NaiveDate;
+    use semver::{Version, VersionReq};
+    use std::fs;
+
+    #[test]
+    fn partition_ignores_separates_active_from_expired() {
+        let active = IgnoreAdvisoryId {
+            id: "RUSTSEC-2020-0001".parse().unwrap(),
+            reason: None,
+            expires: None,
+        };
+        let expired = IgnoreAdvisoryId {
+            id: "RUSTSEC-2020-0002".parse().unwrap(),
+            reason: Some("waiting on upstream patch".into()),
+            expires: Some(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap()),
+        };
+
+        let (active_ids, summary) = partition_ignores(
+            &[active.clone(), expired.clone()],
+            NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(),
+        );
+
+        assert_eq!(active_ids, vec![active.id]);
+        assert_eq!(summary.active, 1);
+        assert_eq!(summary.expired, vec![expired.id]);
+    }
+
+    #[test]
+    fn walk_advisory_toml_files_finds_nested_toml_files_only() {
+        let dir = std::env::temp_dir().join(format!("cargo-audit-test-{}", std::process::id()));
+        fs::create_dir_all(dir.join("crates/foo")).unwrap();
+        fs::write(dir.join("crates/foo/RUSTSEC-2020-0001.toml"), "").unwrap();
+        fs::write(dir.join("README.md"), "").unwrap();
+
+        let found = walk_advisory_toml_files(&dir);
+
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(found, vec![dir.join("crates/foo/RUSTSEC-2020-0001.toml")]);
+    }
+
+    #[test]
+    fn classify_downgrades_findings_below_the_severity_threshold() {
+        let level = classify(LintLevel::Deny, Some(Severity::Low), Some(Severity::High));
+        assert_eq!(level, LintLevel::Warn);
+    }
+
+    #[test]
+    fn classify_keeps_the_configured_level_at_or_above_the_threshold() {
+        let level = classify(LintLevel::Deny, Some(Severity::High), Some(Severity::High));
+        assert_eq!(level, LintLevel::Deny);
+    }
+
+    #[test]
+    fn classify_keeps_the_configured_level_without_a_threshold() {
+        let level = classify(LintLevel::Warn, Some(Severity::Critical), None);
+        assert_eq!(level, LintLevel::Warn);
+    }
+
+    #[test]
+    fn classify_keeps_the_configured_level_without_a_known_severity() {
+        let level = classify(LintLevel::Deny, None, Some(Severity::High));
+        assert_eq!(level, LintLevel::Deny);
+    }
+
+    fn v(s: &str) -> Version {
+        Version::parse(s).unwrap()
+    }
+
+    fn req(s: &str) -> VersionReq {
+        VersionReq::parse(s).unwrap()
+    }
+
+    #[test]
+    fn compatible_version_req_stays_within_current_major() {
+        let constraint = compatible_version_req(&v("1.2.3"));
+        assert!(constraint.matches(&v("1.9.0")));
+        assert!(!constraint.matches(&v("2.0.0")));
+    }
+
+    #[test]
+    fn select_patched_version_picks_the_lowest_match() {
+        let available = vec![v("1.2.3"), v("1.2.4"), v("1.3.0"), v("2.0.0")];
+        let patched = vec![req(">=1.2.4")];
+
+        let selected = select_patched_version(available, &req("^1.2.3"), &patched);
+        assert_eq!(selected, Some(v("1.2.4")));
+    }
+
+    #[test]
+    fn select_patched_version_none_when_only_a_major_bump_is_patched() {
+        let available = vec![v("1.2.3"), v("2.0.0")];
+        let patched = vec![req(">=2.0.0")];
+
+        let selected = select_patched_version(available, &req("^1.2.3"), &patched);
+        assert_eq!(selected, None);
+    }
+
+    #[test]
+    fn dedup_by_keeps_first_occurrence_per_key() {
+        let items = vec![("RUSTSEC-2020-0001", "db-a"), ("RUSTSEC-2020-0001", "db-b"), ("RUSTSEC-2021-0002", "db-a")];
+
+        let deduped = dedup_by(items, |(id, _)| id.to_string());
+
+        assert_eq!(
+            deduped,
+            vec![("RUSTSEC-2020-0001", "db-a"), ("RUSTSEC-2021-0002", "db-a")]
+        );
+    }
+
+    #[test]
+    fn dedup_by_is_a_no_op_when_keys_are_unique() {
+        let items = vec![1, 2, 3];
+        assert_eq!(dedup_by(items, |n| *n), vec![1, 2, 3]);
+    }
+}
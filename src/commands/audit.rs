@@ -3,8 +3,9 @@
 use super::CargoAuditCommand;
 use crate::{
     auditor::Auditor,
-    config::{AuditConfig, OutputFormat},
+    config::{AuditConfig, IgnoreAdvisoryId, LintLevel, OutputFormat},
     prelude::*,
+    sarif,
 };
 use abscissa_core::{config::Override, FrameworkError};
 use gumdrop::Options;
@@ -33,13 +34,14 @@ pub struct AuditCommand {
     )]
     color: Option<String>,
 
-    /// Filesystem path to the advisory database git repository
+    /// Filesystem path(s) to the advisory database git repository
     #[options(
         short = "D",
         long = "db",
-        help = "advisory database git repo path (default: ~/.cargo/advisory-db)"
+        meta = "PATH",
+        help = "advisory database git repo path (default: ~/.cargo/advisory-db, can be specified multiple times)"
     )]
-    db: Option<String>,
+    db: Vec<String>,
 
     /// Path to the lockfile
     #[options(
@@ -54,10 +56,37 @@ pub struct AuditCommand {
         no_short,
         long = "ignore",
         meta = "ADVISORY_ID",
-        help = "Advisory id to ignore (can be specified multiple times)"
+        help = "Advisory id to ignore (can be specified multiple times). For a documented, \
+                expiring exemption, use the `[[ignore]]` table in the config file instead"
     )]
     ignore: Vec<String>,
 
+    /// Advisory categories to treat as errors
+    #[options(
+        no_short,
+        long = "deny",
+        meta = "CATEGORY",
+        help = "exit with an error if a finding of this category is found: vulnerability, unmaintained, unsound, yanked, notice (can be specified multiple times)"
+    )]
+    deny: Vec<String>,
+
+    /// Advisory categories to only warn about
+    #[options(
+        no_short,
+        long = "warn",
+        meta = "CATEGORY",
+        help = "print but don't fail on findings of this category (can be specified multiple times)"
+    )]
+    warn: Vec<String>,
+
+    /// CVSS severity below which denied vulnerabilities are downgraded to warnings
+    #[options(
+        no_short,
+        long = "severity",
+        help = "downgrade vulnerabilities below this CVSS severity to warnings: none, low, medium, high, critical"
+    )]
+    severity: Option<String>,
+
     /// Skip fetching the advisory database git repository
     #[options(
         short = "n",
@@ -66,6 +95,14 @@ pub struct AuditCommand {
     )]
     no_fetch: bool,
 
+    /// Fully offline mode: load advisories from disk, never touch the network
+    #[options(
+        no_short,
+        long = "offline",
+        help = "fully offline mode: load advisories from a local directory tree via `--db` and never touch the network"
+    )]
+    offline: bool,
+
     /// Allow stale advisory databases that haven't been recently updated
     #[options(no_short, long = "stale", help = "allow stale database")]
     stale: bool,
@@ -86,9 +123,14 @@ pub struct AuditCommand {
     )]
     target_os: Option<OS>,
 
-    /// URL to the advisory database git repository
-    #[options(short = "u", long = "url", help = "URL for advisory database git repo")]
-    url: Option<String>,
+    /// URL(s) to the advisory database git repository
+    #[options(
+        short = "u",
+        long = "url",
+        meta = "URL",
+        help = "URL for advisory database git repo (can be specified multiple times)"
+    )]
+    url: Vec<String>,
 
     /// Quiet mode - avoids printing extraneous information
     #[options(
@@ -101,6 +143,30 @@ pub struct AuditCommand {
     /// Output reports as JSON
     #[options(no_short, long = "json", help = "Output report in JSON format")]
     output_json: bool,
+
+    /// Output reports as SARIF for code-scanning integrations
+    #[options(
+        no_short,
+        long = "sarif",
+        help = "Output report as a SARIF 2.1.0 document"
+    )]
+    output_sarif: bool,
+
+    /// Attempt to automatically fix vulnerable dependencies
+    #[options(
+        no_short,
+        long = "fix",
+        help = "automatically upgrade vulnerable dependencies in Cargo.lock to a patched version"
+    )]
+    fix: bool,
+
+    /// Print the fixes that would be applied without writing them
+    #[options(
+        no_short,
+        long = "dry-run",
+        help = "print the version bumps `--fix` would apply without writing Cargo.lock"
+    )]
+    dry_run: bool,
 }
 
 impl Override<AuditConfig> for AuditCommand {
@@ -109,20 +175,72 @@ impl Override<AuditConfig> for AuditCommand {
             config.color = Some(color.clone());
         }
 
-        if let Some(db) = &self.db {
-            config.advisory_db_path = Some(db.into());
+        for db in &self.db {
+            config.advisory_db_paths.push(db.into());
         }
 
         for advisory_id in &self.ignore {
             // TODO(tarcieri): handle/ignore duplicate advisory IDs between config and CLI opts
-            config.ignore.push(advisory_id.parse().unwrap_or_else(|e| {
+            let id = advisory_id.parse().unwrap_or_else(|e| {
                 status_err!("error parsing {}: {}", advisory_id, e);
                 exit(1);
+            });
+
+            // Ignores passed on the CLI have no reason or expiry attached;
+            // those are only settable via the config file's ignore table.
+            config.ignore.push(IgnoreAdvisoryId {
+                id,
+                reason: None,
+                expires: None,
+            });
+        }
+
+        for category in self.warn.iter().filter(|c| self.deny.contains(c)) {
+            status_err!(
+                "category `{}` given to both --deny and --warn",
+                category
+            );
+            exit(1);
+        }
+
+        for category in &self.deny {
+            config.lint.set(category, LintLevel::Deny).unwrap_or_else(|e| {
+                status_err!("error parsing --deny category: {}", e);
+                exit(1);
+            });
+        }
+
+        for category in &self.warn {
+            config.lint.set(category, LintLevel::Warn).unwrap_or_else(|e| {
+                status_err!("error parsing --warn category: {}", e);
+                exit(1);
+            });
+        }
+
+        if let Some(severity) = &self.severity {
+            config.severity_threshold = Some(severity.parse().unwrap_or_else(|e| {
+                status_err!("error parsing --severity {}: {}", severity, e);
+                exit(1);
             }));
         }
 
         config.no_fetch |= self.no_fetch;
         config.allow_stale |= self.stale;
+        config.offline |= self.offline;
+
+        if config.offline {
+            if config.advisory_db_paths.is_empty() {
+                status_err!(
+                    "--offline requires at least one `--db PATH` pointing at a local advisory directory"
+                );
+                exit(1);
+            }
+
+            // Offline mode never touches git, so fetching and staleness
+            // checks (which assume a git-backed advisory DB) don't apply.
+            config.no_fetch = true;
+            config.allow_stale = true;
+        }
 
         if let Some(target_arch) = self.target_arch {
             config.target_arch = Some(target_arch);
@@ -132,16 +250,25 @@ impl Override<AuditConfig> for AuditCommand {
             config.target_os = Some(target_os);
         }
 
-        if let Some(url) = &self.url {
-            config.advisory_db_url = Some(url.clone())
+        for url in &self.url {
+            config.advisory_db_urls.push(url.clone());
         }
 
         config.quiet |= self.quiet;
 
+        if self.output_json && self.output_sarif {
+            status_err!("--json and --sarif are mutually exclusive output formats");
+            exit(1);
+        }
+
         if self.output_json {
             config.output_format = OutputFormat::Json;
         }
 
+        if self.output_sarif {
+            config.output_format = OutputFormat::Sarif;
+        }
+
         Ok(config)
     }
 }
@@ -157,13 +284,93 @@ impl Runnable for AuditCommand {
             exit(0);
         }
 
+        if self.dry_run && !self.fix {
+            status_err!("--dry-run has no effect without --fix");
+            exit(1);
+        }
+
         let lockfile_path = self
             .file
             .as_ref()
             .map(PathBuf::from)
             .unwrap_or_else(|| PathBuf::from(CARGO_LOCK_FILE));
 
-        self.auditor().audit(&lockfile_path);
+        let auditor = self.auditor();
+        let report = auditor.audit(&lockfile_path);
+        let mut lint_outcome = auditor.lint(&report);
+
+        if self.fix {
+            let fix_report = auditor.fix(&lockfile_path, &report, self.dry_run);
+
+            // Re-audit the rewritten lockfile so the exit code reflects
+            // what's still vulnerable after remediation, not the pre-fix
+            // findings: a fully successful fix should exit 0.
+            if !self.dry_run && !fix_report.fixes.is_empty() {
+                lint_outcome = auditor.lint(&auditor.audit(&lockfile_path));
+            }
+        }
+
+        if app_config().output_format == OutputFormat::Terminal && !self.quiet {
+            for vuln in &report.vulnerabilities {
+                println!(
+                    "\nCrate:     {}\nVersion:   {}\nTitle:     {}\nDate:      {}\nID:        {}\nURL:       {}",
+                    vuln.package.name,
+                    vuln.package.version,
+                    vuln.advisory.title,
+                    vuln.advisory.date,
+                    vuln.advisory.id,
+                    vuln.advisory
+                        .url
+                        .as_ref()
+                        .map(ToString::to_string)
+                        .unwrap_or_else(|| "n/a".to_string()),
+                );
+            }
+        }
+
+        for deny in &lint_outcome.deny {
+            status_err!("{}", deny);
+        }
+
+        for warning in &lint_outcome.warn {
+            status_warn!("{}", warning);
+        }
+
+        // Only the Terminal report mixes human status lines into stdout;
+        // Json and Sarif print a single machine-readable document there so
+        // downstream consumers get a parseable stream.
+        match app_config().output_format {
+            OutputFormat::Terminal => {
+                if !self.quiet {
+                    println!(
+                        "\nignore exemptions: {} active, {} expired",
+                        report.exemptions.active,
+                        report.exemptions.expired.len()
+                    );
+                }
+            }
+            OutputFormat::Json => {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&report).unwrap_or_else(|e| {
+                        status_err!("couldn't serialize JSON report: {}", e);
+                        exit(1);
+                    })
+                );
+            }
+            OutputFormat::Sarif => {
+                let sarif_log = sarif::report_to_sarif(&report);
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&sarif_log).unwrap_or_else(|e| {
+                        status_err!("couldn't serialize SARIF report: {}", e);
+                        exit(1);
+                    })
+                );
+            }
+        }
+
+        exit(lint_outcome.exit_code());
     }
 }
 
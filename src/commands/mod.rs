@@ -0,0 +1,33 @@
+//! `cargo-audit` subcommands
+
+mod audit;
+
+pub use self::audit::AuditCommand;
+
+use crate::config::AuditConfig;
+use abscissa_core::{config::Override, Command, Configurable, FrameworkError, Runnable};
+use std::path::PathBuf;
+
+/// `cargo audit` entry point: `cargo-audit audit ...`
+///
+/// `cargo` invokes third-party subcommands as `cargo-<name> <name> ...`, so
+/// the top-level command here is just a thin wrapper around `audit`.
+#[derive(Command, Debug, Runnable)]
+pub enum CargoAuditCommand {
+    /// The `cargo audit` subcommand
+    Audit(AuditCommand),
+}
+
+impl Configurable<AuditConfig> for CargoAuditCommand {
+    fn config_path(&self) -> Option<PathBuf> {
+        None
+    }
+}
+
+impl Override<AuditConfig> for CargoAuditCommand {
+    fn override_config(&self, config: AuditConfig) -> Result<AuditConfig, FrameworkError> {
+        match self {
+            Self::Audit(cmd) => cmd.override_config(config),
+        }
+    }
+}
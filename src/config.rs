@@ -0,0 +1,299 @@
+//! `cargo audit` configuration
+
+use chrono::NaiveDate;
+use rustsec::advisory::Id as AdvisoryId;
+use rustsec::platforms::target::{Arch, OS};
+use serde::{Deserialize, Serialize};
+use std::{path::PathBuf, str::FromStr};
+
+/// Configuration for `cargo audit`
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct AuditConfig {
+    /// Color configuration
+    #[serde(default)]
+    pub color: Option<String>,
+
+    /// Filesystem paths to advisory database git repositories (or, in
+    /// `--offline` mode, plain directories of advisory TOML files).
+    ///
+    /// When empty, the default RustSec DB path (`~/.cargo/advisory-db`) is
+    /// used. Multiple entries are queried together: the `Auditor` loads
+    /// each one and merges the resulting matches, de-duplicating by
+    /// advisory ID and affected package version so a vulnerability
+    /// reported by more than one database is only reported once.
+    #[serde(default)]
+    pub advisory_db_paths: Vec<PathBuf>,
+
+    /// URLs to advisory database git repositories, parallel to
+    /// `advisory_db_paths` (by position) for any path which doesn't
+    /// already exist locally and needs to be fetched.
+    #[serde(default)]
+    pub advisory_db_urls: Vec<String>,
+
+    /// Advisories to ignore, with optional documentation of why and a
+    /// lapse date
+    #[serde(default)]
+    pub ignore: Vec<IgnoreAdvisoryId>,
+
+    /// Skip fetching the advisory database git repository
+    #[serde(default)]
+    pub no_fetch: bool,
+
+    /// Allow stale advisory databases that haven't been recently updated
+    #[serde(default)]
+    pub allow_stale: bool,
+
+    /// Fully offline mode: load advisories straight from a directory tree
+    /// on disk (via `advisory_db_paths`/`--db`) and never touch the
+    /// network or a git repository
+    #[serde(default)]
+    pub offline: bool,
+
+    /// Target CPU architecture to find vulnerabilities for
+    #[serde(default)]
+    pub target_arch: Option<Arch>,
+
+    /// Target OS to find vulnerabilities for
+    #[serde(default)]
+    pub target_os: Option<OS>,
+
+    /// Per-category lint levels (deny/warn/allow), following the model
+    /// used by `deny.toml`
+    #[serde(default)]
+    pub lint: LintConfig,
+
+    /// CVSS severity threshold: `deny`-level vulnerabilities with a known
+    /// severity below this are downgraded to a warning (`warn` and `allow`
+    /// levels are left as they are)
+    #[serde(default)]
+    pub severity_threshold: Option<Severity>,
+
+    /// Quiet mode - avoids printing extraneous information
+    #[serde(default)]
+    pub quiet: bool,
+
+    /// Output format to use
+    #[serde(default)]
+    pub output_format: OutputFormat,
+}
+
+/// Output format to use when printing a report
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Display report as formatted terminal output
+    Terminal,
+
+    /// Display report as JSON
+    Json,
+
+    /// Display report as a SARIF 2.1.0 document, for code-scanning
+    /// dashboards that consume it but can't feed from the JSON schema
+    Sarif,
+}
+
+impl Default for OutputFormat {
+    fn default() -> OutputFormat {
+        OutputFormat::Terminal
+    }
+}
+
+/// Lint level for an advisory category: whether a match should fail the
+/// build (`deny`), be printed without failing (`warn`), or be skipped
+/// entirely (`allow`)
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    /// Exit with an error if this category is found
+    Deny,
+
+    /// Print findings in this category, but don't fail
+    Warn,
+
+    /// Ignore findings in this category entirely
+    Allow,
+}
+
+impl FromStr for LintLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "deny" => Ok(LintLevel::Deny),
+            "warn" => Ok(LintLevel::Warn),
+            "allow" => Ok(LintLevel::Allow),
+            other => Err(format!(
+                "invalid lint level `{}` (expected `deny`, `warn`, or `allow`)",
+                other
+            )),
+        }
+    }
+}
+
+/// Per-category lint levels, settable individually in the config file and
+/// overridable per-run via `--deny CATEGORY`/`--warn CATEGORY`
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct LintConfig {
+    /// Security vulnerabilities reported against a dependency
+    #[serde(default = "LintConfig::default_vulnerability")]
+    pub vulnerability: LintLevel,
+
+    /// Dependencies flagged as unmaintained
+    #[serde(default = "LintConfig::default_informational")]
+    pub unmaintained: LintLevel,
+
+    /// Dependencies flagged as unsound
+    #[serde(default = "LintConfig::default_informational")]
+    pub unsound: LintLevel,
+
+    /// Dependencies yanked from their registry
+    #[serde(default = "LintConfig::default_informational")]
+    pub yanked: LintLevel,
+
+    /// General informational notices
+    #[serde(default = "LintConfig::default_informational")]
+    pub notice: LintLevel,
+}
+
+impl LintConfig {
+    fn default_vulnerability() -> LintLevel {
+        LintLevel::Deny
+    }
+
+    fn default_informational() -> LintLevel {
+        LintLevel::Warn
+    }
+
+    /// Set the lint level for a named category, as given to `--deny`/`--warn`
+    pub fn set(&mut self, category: &str, level: LintLevel) -> Result<(), String> {
+        match category {
+            "vulnerability" => self.vulnerability = level,
+            "unmaintained" => self.unmaintained = level,
+            "unsound" => self.unsound = level,
+            "yanked" => self.yanked = level,
+            "notice" => self.notice = level,
+            other => return Err(format!("unknown advisory category `{}`", other)),
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        Self {
+            vulnerability: Self::default_vulnerability(),
+            unmaintained: Self::default_informational(),
+            unsound: Self::default_informational(),
+            yanked: Self::default_informational(),
+            notice: Self::default_informational(),
+        }
+    }
+}
+
+/// CVSS severity of a finding, also used as the `--severity` threshold
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// No CVSS score / severity associated with the finding
+    None,
+
+    /// CVSS "low" severity
+    Low,
+
+    /// CVSS "medium" severity
+    Medium,
+
+    /// CVSS "high" severity
+    High,
+
+    /// CVSS "critical" severity
+    Critical,
+}
+
+impl FromStr for Severity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "none" => Ok(Severity::None),
+            "low" => Ok(Severity::Low),
+            "medium" => Ok(Severity::Medium),
+            "high" => Ok(Severity::High),
+            "critical" => Ok(Severity::Critical),
+            other => Err(format!(
+                "invalid severity `{}` (expected none, low, medium, high, or critical)",
+                other
+            )),
+        }
+    }
+}
+
+/// An advisory ID to ignore, documented with an optional reason and/or a
+/// date after which the exemption lapses. Following the exemption model
+/// used by supply-chain tooling, this keeps suppressions from silently
+/// accumulating forever: once `expires` has passed the `Auditor` stops
+/// honoring the entry and surfaces the advisory again.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct IgnoreAdvisoryId {
+    /// Advisory ID being ignored
+    pub id: AdvisoryId,
+
+    /// Why this advisory is being ignored, for whoever reads the config next
+    #[serde(default)]
+    pub reason: Option<String>,
+
+    /// Date after which this exemption should no longer apply
+    #[serde(default)]
+    pub expires: Option<NaiveDate>,
+}
+
+impl IgnoreAdvisoryId {
+    /// Whether this exemption's `expires` date, if any, has passed as of `today`
+    pub fn is_expired(&self, today: NaiveDate) -> bool {
+        self.expires.map_or(false, |expires| today > expires)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IgnoreAdvisoryId, LintConfig, LintLevel};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn lint_config_set_overrides_the_named_category() {
+        let mut lint = LintConfig::default();
+        assert_eq!(lint.unmaintained, LintLevel::Warn);
+
+        lint.set("unmaintained", LintLevel::Deny).unwrap();
+        assert_eq!(lint.unmaintained, LintLevel::Deny);
+    }
+
+    #[test]
+    fn lint_config_set_rejects_unknown_categories() {
+        let mut lint = LintConfig::default();
+        assert!(lint.set("typo", LintLevel::Deny).is_err());
+    }
+
+    fn ignore_entry(expires: Option<&str>) -> IgnoreAdvisoryId {
+        IgnoreAdvisoryId {
+            id: "RUSTSEC-2020-0001".parse().unwrap(),
+            reason: None,
+            expires: expires.map(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").unwrap()),
+        }
+    }
+
+    #[test]
+    fn is_expired_false_without_an_expiry_date() {
+        assert!(!ignore_entry(None).is_expired(NaiveDate::from_ymd_opt(2099, 1, 1).unwrap()));
+    }
+
+    #[test]
+    fn is_expired_true_once_past_the_expiry_date() {
+        let entry = ignore_entry(Some("2020-01-01"));
+        assert!(entry.is_expired(NaiveDate::from_ymd_opt(2020, 1, 2).unwrap()));
+        assert!(!entry.is_expired(NaiveDate::from_ymd_opt(2019, 12, 31).unwrap()));
+    }
+}
@@ -0,0 +1,11 @@
+//! `cargo-audit`: audit `Cargo.lock` files for crates with security
+//! vulnerabilities reported to the RustSec Advisory Database.
+
+pub mod application;
+pub mod auditor;
+pub mod commands;
+pub mod config;
+pub mod prelude;
+pub mod sarif;
+
+pub use crate::{application::APP, auditor::Auditor, config::AuditConfig};
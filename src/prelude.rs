@@ -0,0 +1,14 @@
+//! A prelude of commonly used types and traits, imported by
+//! `commands/audit.rs` and `auditor.rs`.
+
+pub use crate::application::APP;
+pub use abscissa_core::{status_err, status_warn, Command, Runnable};
+
+use abscissa_core::Application;
+
+use crate::config::AuditConfig;
+
+/// Get the current `AuditConfig`
+pub fn app_config() -> std::sync::Arc<AuditConfig> {
+    APP.config()
+}
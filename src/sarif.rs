@@ -0,0 +1,215 @@
+//! SARIF 2.1.0 report serialization, for uploading `cargo audit` findings
+//! to code-scanning dashboards that consume SARIF but can't feed from the
+//! crate's own JSON schema.
+
+use crate::auditor::Report;
+use rustsec::Advisory;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+const SARIF_VERSION: &str = "2.1.0";
+const SARIF_SCHEMA: &str =
+    "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json";
+
+/// Top-level SARIF log
+#[derive(Debug, Serialize)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    pub schema: String,
+    pub version: String,
+    pub runs: Vec<Run>,
+}
+
+/// A single SARIF run: one tool invocation and its results
+#[derive(Debug, Serialize)]
+pub struct Run {
+    pub tool: Tool,
+    pub results: Vec<SarifResult>,
+}
+
+/// The tool that produced a run
+#[derive(Debug, Serialize)]
+pub struct Tool {
+    pub driver: Driver,
+}
+
+/// Describes `cargo-audit` itself, plus the advisory rules it checked
+#[derive(Debug, Serialize)]
+pub struct Driver {
+    pub name: String,
+    #[serde(rename = "informationUri")]
+    pub information_uri: String,
+    pub version: String,
+    pub rules: Vec<Rule>,
+}
+
+/// A SARIF rule: one per distinct advisory matched
+#[derive(Debug, Serialize)]
+pub struct Rule {
+    pub id: String,
+    pub name: String,
+    #[serde(rename = "helpUri", skip_serializing_if = "Option::is_none")]
+    pub help_uri: Option<String>,
+    #[serde(rename = "shortDescription")]
+    pub short_description: SarifText,
+    #[serde(rename = "fullDescription")]
+    pub full_description: SarifText,
+    pub properties: RuleProperties,
+}
+
+/// Rule properties carrying the advisory's CVSS score, so scanning
+/// dashboards can sort/filter by severity
+#[derive(Debug, Serialize)]
+pub struct RuleProperties {
+    #[serde(rename = "security-severity")]
+    pub security_severity: String,
+}
+
+/// A plain SARIF message/description wrapper
+#[derive(Debug, Serialize)]
+pub struct SarifText {
+    pub text: String,
+}
+
+/// A SARIF result: one per affected lockfile package
+#[derive(Debug, Serialize)]
+pub struct SarifResult {
+    #[serde(rename = "ruleId")]
+    pub rule_id: String,
+    pub level: String,
+    pub message: SarifText,
+    pub locations: Vec<Location>,
+}
+
+/// Where a result was found; `cargo audit` has no source spans to point
+/// at, so this always points at `Cargo.lock` itself
+#[derive(Debug, Serialize)]
+pub struct Location {
+    #[serde(rename = "physicalLocation")]
+    pub physical_location: PhysicalLocation,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PhysicalLocation {
+    #[serde(rename = "artifactLocation")]
+    pub artifact_location: ArtifactLocation,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ArtifactLocation {
+    pub uri: String,
+}
+
+/// Convert an audit `Report` into a SARIF 2.1.0 log: one `rule` per
+/// distinct advisory matched, and one `result` per affected lockfile
+/// package.
+pub fn report_to_sarif(report: &Report) -> SarifLog {
+    let mut rules_by_id = BTreeMap::new();
+    let mut results = vec![];
+
+    for vuln in &report.vulnerabilities {
+        let advisory = &vuln.advisory;
+
+        rules_by_id
+            .entry(advisory.id.to_string())
+            .or_insert_with(|| advisory_rule(advisory));
+
+        results.push(SarifResult {
+            rule_id: advisory.id.to_string(),
+            level: sarif_level(advisory),
+            message: SarifText {
+                text: format!(
+                    "{} {}: {}",
+                    vuln.package.name, vuln.package.version, advisory.description
+                ),
+            },
+            locations: vec![Location {
+                physical_location: PhysicalLocation {
+                    artifact_location: ArtifactLocation {
+                        uri: "Cargo.lock".to_string(),
+                    },
+                },
+            }],
+        });
+    }
+
+    SarifLog {
+        schema: SARIF_SCHEMA.to_string(),
+        version: SARIF_VERSION.to_string(),
+        runs: vec![Run {
+            tool: Tool {
+                driver: Driver {
+                    name: "cargo-audit".to_string(),
+                    information_uri: "https://github.com/RustSec/rustsec".to_string(),
+                    version: env!("CARGO_PKG_VERSION").to_string(),
+                    rules: rules_by_id.into_values().collect(),
+                },
+            },
+            results,
+        }],
+    }
+}
+
+/// Build the SARIF `rule` for a single advisory
+fn advisory_rule(advisory: &Advisory) -> Rule {
+    Rule {
+        id: advisory.id.to_string(),
+        name: advisory.title.clone(),
+        help_uri: advisory.url.as_ref().map(ToString::to_string),
+        short_description: SarifText {
+            text: advisory.title.clone(),
+        },
+        full_description: SarifText {
+            text: advisory.description.clone(),
+        },
+        properties: RuleProperties {
+            security_severity: advisory
+                .metadata
+                .cvss
+                .as_ref()
+                .map(|cvss| cvss.score().value().to_string())
+                .unwrap_or_else(|| "0.0".to_string()),
+        },
+    }
+}
+
+/// Map an advisory's CVSS severity to a SARIF result `level`
+fn sarif_level(advisory: &Advisory) -> String {
+    severity_to_sarif_level(advisory.metadata.cvss.as_ref().map(|cvss| cvss.severity())).to_string()
+}
+
+/// Pure mapping from CVSS severity to SARIF `level`, split out so it can
+/// be unit tested without constructing a full `Advisory`.
+fn severity_to_sarif_level(severity: Option<rustsec::cvss::Severity>) -> &'static str {
+    use rustsec::cvss::Severity::*;
+
+    match severity {
+        Some(Critical) | Some(High) => "error",
+        Some(Medium) => "warning",
+        Some(Low) | Some(None) | None => "note",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::severity_to_sarif_level;
+    use rustsec::cvss::Severity;
+
+    #[test]
+    fn maps_high_and_critical_to_error() {
+        assert_eq!(severity_to_sarif_level(Some(Severity::High)), "error");
+        assert_eq!(severity_to_sarif_level(Some(Severity::Critical)), "error");
+    }
+
+    #[test]
+    fn maps_medium_to_warning() {
+        assert_eq!(severity_to_sarif_level(Some(Severity::Medium)), "warning");
+    }
+
+    #[test]
+    fn maps_low_and_missing_to_note() {
+        assert_eq!(severity_to_sarif_level(Some(Severity::Low)), "note");
+        assert_eq!(severity_to_sarif_level(Some(Severity::None)), "note");
+        assert_eq!(severity_to_sarif_level(None), "note");
+    }
+}